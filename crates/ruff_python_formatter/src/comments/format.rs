@@ -0,0 +1,83 @@
+use ruff_text_size::Ranged;
+
+use crate::comments::type_comment::format_type_comment;
+use crate::comments::SourceComment;
+use crate::preview::is_type_comment_formatting_enabled;
+use crate::prelude::*;
+
+/// Renders a single comment's text.
+///
+/// When [`is_type_comment_formatting_enabled`] and `comment` is classified as
+/// [`crate::comments::SourceComment::kind`] `Type`, the payload is parsed and
+/// reformatted as a type expression (see
+/// `crate::comments::type_comment::format_type_comment`); otherwise, and on
+/// any parse failure, the comment is emitted verbatim.
+pub(crate) fn format_comment(comment: &SourceComment, f: &mut PyFormatter) -> FormatResult<()> {
+    if is_type_comment_formatting_enabled(f.context()) {
+        let verbatim = f.context().locator().slice(comment.range());
+
+        if let Some(formatted) =
+            format_type_comment(verbatim, comment.kind(), f.context().options())
+        {
+            return text(&formatted, Some(comment.range().start())).fmt(f);
+        }
+    }
+
+    source_text_slice(comment.range()).fmt(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{format_module_source, PreviewMode, PyFormatOptions, QuoteStyle};
+
+    fn format_with_preview(source: &str, preview: PreviewMode) -> String {
+        format_module_source(source, PyFormatOptions::default().with_preview(preview))
+            .unwrap()
+            .as_code()
+            .to_string()
+    }
+
+    #[test]
+    fn type_comment_is_normalized_end_to_end() {
+        let source = "x = []  #type:list[int]\n";
+
+        let formatted = format_with_preview(source, PreviewMode::Enabled);
+
+        assert!(
+            formatted.contains("# type: list[int]"),
+            "expected a normalized type comment in {formatted:?}"
+        );
+    }
+
+    #[test]
+    fn type_comment_respects_configured_quote_style() {
+        let source = "x = []  #type:Literal['a']\n";
+
+        let formatted = format_module_source(
+            source,
+            PyFormatOptions::default()
+                .with_preview(PreviewMode::Enabled)
+                .with_quote_style(QuoteStyle::Single),
+        )
+        .unwrap()
+        .as_code()
+        .to_string();
+
+        assert!(
+            formatted.contains("# type: Literal['a']"),
+            "expected the file's single-quote style to carry into the type comment, got {formatted:?}"
+        );
+    }
+
+    #[test]
+    fn type_comment_is_left_verbatim_outside_preview() {
+        let source = "x = []  #type:list[int]\n";
+
+        let formatted = format_with_preview(source, PreviewMode::Disabled);
+
+        assert!(
+            formatted.contains("#type:list[int]"),
+            "expected the verbatim comment in {formatted:?}"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use ruff_python_index::comment_kind::PyCommentKind;
+use ruff_text_size::TextRange;
+
+use crate::prelude::*;
+
+mod format;
+mod type_comment;
+
+pub(crate) use format::format_comment;
+
+/// A `#` comment from the source, paired with the [`PyCommentKind`]
+/// classification computed once up front by `CommentRangesBuilder` so the
+/// rendering path doesn't need to re-scan the comment text.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SourceComment {
+    range: TextRange,
+    kind: PyCommentKind,
+}
+
+impl SourceComment {
+    pub(crate) fn new(range: TextRange, kind: PyCommentKind) -> Self {
+        Self { range, kind }
+    }
+
+    pub(crate) fn range(&self) -> TextRange {
+        self.range
+    }
+
+    pub(crate) fn kind(&self) -> PyCommentKind {
+        self.kind
+    }
+}
+
+impl Format<PyFormatContext<'_>> for SourceComment {
+    fn fmt(&self, f: &mut PyFormatter) -> FormatResult<()> {
+        format_comment(self, f)
+    }
+}
@@ -0,0 +1,257 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use ruff_python_index::comment_kind::PyCommentKind;
+
+use crate::{MagicTrailingComma, PyFormatOptions};
+
+/// Matches an embedded `# type: ignore` suffix within a combined comment like
+/// `# type: int  # type: ignore[assignment]`, tolerating the same whitespace
+/// variants `PyCommentKind::from_comment`'s classifier does (e.g.
+/// `#type:ignore`, `type:   ignore`).
+static EMBEDDED_TYPE_IGNORE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"#\s*type:\s*ignore").unwrap());
+
+/// Reformats the payload of a PEP 484 inline type comment (`# type: List[int]`)
+/// as a normalized type expression, e.g. `#type:dict[str,int]` becomes
+/// `# type: dict[str, int]`.
+///
+/// Called from the comment-normalization path for any comment `kind` returns
+/// as [`PyCommentKind::Type`] (see `CommentRangesBuilder`), guarded by
+/// `crate::preview::is_type_comment_formatting_enabled`. `options` is the
+/// current file's formatting options, so the reformatted expression respects
+/// e.g. the configured line-length and quote-style instead of defaults.
+///
+/// Returns `None` when `comment` isn't classified as [`PyCommentKind::Type`],
+/// or when the payload doesn't parse as a type expression -- in either case
+/// the comment is left untouched so we never corrupt a non-conforming comment.
+pub(crate) fn format_type_comment(
+    comment: &str,
+    kind: PyCommentKind,
+    options: &PyFormatOptions,
+) -> Option<String> {
+    if kind != PyCommentKind::Type {
+        return None;
+    }
+
+    let colon = comment.find("type:")? + "type:".len();
+    let payload = &comment[colon..];
+
+    // A combined comment like `# type: int  # type: ignore[assignment]` keeps
+    // its ignore suffix untouched; only the leading type expression is
+    // reformatted.
+    let (type_src, ignore_suffix) = match EMBEDDED_TYPE_IGNORE_RE.find(payload) {
+        Some(ignore_match) => (
+            &payload[..ignore_match.start()],
+            payload[ignore_match.start()..].trim_end(),
+        ),
+        None => (payload, ""),
+    };
+
+    let formatted = if let Some((args, returns)) = split_signature_comment(type_src) {
+        let args = args.trim();
+        // `()` is a valid (empty tuple) signature payload for a no-argument
+        // function, e.g. `# type: () -> None`. There's no expression to
+        // format, so emit it literally rather than routing an empty string
+        // through `format_type_expression`, which has nothing to parse.
+        let formatted_args = if args.is_empty() {
+            String::new()
+        } else {
+            // A bare `int, str` isn't a valid annotation, so format the
+            // argument list as a subscript slice and peel the subscript off.
+            let subscript = format_type_expression(&format!("_[{args}]"), options)?;
+            subscript.strip_prefix("_[")?.strip_suffix(']')?.to_string()
+        };
+        format!(
+            "({formatted_args}) -> {}",
+            format_type_expression(returns.trim(), options)?
+        )
+    } else {
+        format_type_expression(type_src.trim(), options)?
+    };
+
+    Some(if ignore_suffix.is_empty() {
+        format!("# type: {formatted}")
+    } else {
+        format!("# type: {formatted}  {ignore_suffix}")
+    })
+}
+
+/// Splits the function-signature form of a type comment, e.g.
+/// `(int, str) -> bool`, into its argument tuple and return type.
+///
+/// Finds the `)` that matches the leading `(` by tracking nesting depth,
+/// rather than just the last `)` in the string, so a parenthesized return
+/// type (`(int) -> (str, bool)`) doesn't get misread as part of the argument
+/// list.
+fn split_signature_comment(type_src: &str) -> Option<(&str, &str)> {
+    let trimmed = type_src.trim();
+    let rest = trimmed.strip_prefix('(')?;
+
+    let mut depth = 1i32;
+    let close = rest.char_indices().find_map(|(i, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(i)
+    })?;
+
+    let args = &rest[..close];
+    let returns = rest[close + 1..].trim().strip_prefix("->")?;
+    Some((args, returns))
+}
+
+/// Parses `source` as a type expression and re-emits it through the
+/// expression formatter, e.g. `dict[str,int]` becomes `dict[str, int]`.
+///
+/// Returns `None` on any parse error so the caller can fall back to the
+/// verbatim comment text.
+fn format_type_expression(source: &str, options: &PyFormatOptions) -> Option<String> {
+    if source.is_empty() {
+        return None;
+    }
+
+    // There's no dedicated entry point for formatting a bare type expression,
+    // so we wrap it in a synthetic annotated assignment, format that as a
+    // module, and then peel the wrapper back off. Trailing commas inside the
+    // synthetic wrapper have no bearing on the real file, so they're always
+    // collapsed regardless of the file's own magic-trailing-comma setting.
+    let formatted = crate::format_module_source(
+        &format!("_: {source}"),
+        options
+            .clone()
+            .with_magic_trailing_comma(MagicTrailingComma::Ignore),
+    )
+    .ok()?;
+
+    let printed = formatted.as_code();
+    let annotation = printed.strip_prefix("_: ")?.trim_end_matches('\n');
+
+    // If the type expression didn't fit the configured line length, the
+    // formatter would have broken it across multiple lines. Re-emitting that
+    // inside a `#` comment would turn everything after the first embedded
+    // newline into live code, so bail out and keep the comment verbatim.
+    if annotation.contains('\n') {
+        return None;
+    }
+
+    Some(annotation.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_index::comment_kind::PyCommentKind;
+
+    use crate::PyFormatOptions;
+
+    use super::format_type_comment;
+
+    fn format(comment: &str) -> Option<String> {
+        format_type_comment(comment, PyCommentKind::Type, &PyFormatOptions::default())
+    }
+
+    #[test]
+    fn normalizes_simple_type_comment() {
+        assert_eq!(format("# type: List[int]").as_deref(), Some("# type: List[int]"));
+        assert_eq!(
+            format("#type:dict[str,int]").as_deref(),
+            Some("# type: dict[str, int]")
+        );
+    }
+
+    #[test]
+    fn normalizes_function_signature_comment() {
+        assert_eq!(
+            format("# type: (int,str)->bool").as_deref(),
+            Some("# type: (int, str) -> bool")
+        );
+    }
+
+    #[test]
+    fn normalizes_zero_argument_signature_comment() {
+        assert_eq!(
+            format("# type: ()->bool").as_deref(),
+            Some("# type: () -> bool")
+        );
+        assert_eq!(
+            format("# type: () -> None").as_deref(),
+            Some("# type: () -> None")
+        );
+    }
+
+    #[test]
+    fn preserves_trailing_type_ignore_suffix() {
+        assert_eq!(
+            format("# type: List[int]  # type: ignore[assignment]").as_deref(),
+            Some("# type: List[int]  # type: ignore[assignment]")
+        );
+    }
+
+    #[test]
+    fn preserves_trailing_type_ignore_suffix_with_mypys_whitespace_variants() {
+        assert_eq!(
+            format("# type: List[int]  #type:ignore[assignment]").as_deref(),
+            Some("# type: List[int]  #type:ignore[assignment]")
+        );
+    }
+
+    #[test]
+    fn normalizes_signature_comment_with_parenthesized_return_type() {
+        assert_eq!(
+            format("# type: (int) -> (str,bool)").as_deref(),
+            Some("# type: (int) -> (str, bool)")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_verbatim_on_parse_error() {
+        assert_eq!(format("# type:((("), None);
+    }
+
+    #[test]
+    fn falls_back_to_verbatim_when_expression_would_wrap() {
+        // Too long to fit on one line at the default line length, so the
+        // module formatter would wrap it across multiple lines; we must
+        // reject that rather than emit a comment containing a raw `\n`.
+        let long_union = format!(
+            "# type: Union[{}]",
+            (0..40)
+                .map(|i| format!("VeryLongTypeNameNumber{i}"))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        assert_eq!(format(&long_union), None);
+    }
+
+    #[test]
+    fn respects_configured_line_length() {
+        // At ~133 characters, `_: <type>` overflows the default 88-column
+        // width (so this would fall back to verbatim there), but fits
+        // comfortably under a widened one -- it must not be rejected by the
+        // multi-line guard in that case.
+        let union = format!(
+            "# type: Union[{}]",
+            (0..5)
+                .map(|i| format!("VeryLongTypeNameNumber{i}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let wide = PyFormatOptions::default()
+            .with_line_width(ruff_formatter::LineWidth::try_from(200).unwrap());
+
+        assert_eq!(format_type_comment(&union, PyCommentKind::Type, &wide), Some(union));
+    }
+
+    #[test]
+    fn ignores_non_type_comments() {
+        assert_eq!(
+            format_type_comment(
+                "# type: ignore[no-redef]",
+                PyCommentKind::TypeIgnore,
+                &PyFormatOptions::default()
+            ),
+            None
+        );
+    }
+}
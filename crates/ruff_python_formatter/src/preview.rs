@@ -0,0 +1,11 @@
+use crate::PyFormatContext;
+
+/// Returns `true` if the formatter should parse and reformat the payload of
+/// `# type:` comments (see [`crate::comments::type_comment`]).
+///
+/// This is a new, still-settling behavior change, so it's gated the same way
+/// as the rest of the preview style surface until it's been validated against
+/// a large enough corpus of real-world type comments.
+pub(crate) fn is_type_comment_formatting_enabled(context: &PyFormatContext) -> bool {
+    context.options().preview().is_enabled()
+}
@@ -0,0 +1,213 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use ruff_text_size::{TextRange, TextSize};
+
+/// Classification of a `#` comment, computed purely from its text and position.
+///
+/// Modeled on rust-analyzer's `CommentKind::from_text`: tooling directives are
+/// matched against a static prefix table in table order, while `Type` and
+/// `TypeIgnore` are told apart by the exact word that follows `type:`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum PyCommentKind {
+    /// `#!/usr/bin/env python`, only meaningful as the first two bytes of the file.
+    Shebang,
+    /// A PEP 263 encoding declaration, e.g. `# -*- coding: utf-8 -*-`.
+    CodingCookie,
+    /// A PEP 484 inline type comment, e.g. `# type: int`.
+    Type,
+    /// A `# type: ignore[...]` suppression comment.
+    TypeIgnore,
+    /// A tooling pragma that changes the behaviour of some other tool,
+    /// e.g. `# noqa`, `# fmt: off`, `# isort: skip`.
+    Directive,
+    /// Any other comment.
+    #[default]
+    Ordinary,
+}
+
+/// A single entry in the prefix table: the prefix to match (without the
+/// leading `#`) and the kind it produces.
+struct PrefixEntry {
+    prefix: &'static str,
+    kind: PyCommentKind,
+}
+
+/// Prefixes that aren't anchored to a particular line.
+///
+/// `Shebang` and `CodingCookie` are handled separately because they depend on
+/// the comment's *position* (byte offset zero, or one of the first two
+/// physical lines) rather than its text alone. `Type`/`TypeIgnore` are also
+/// handled separately (see `TYPE_COMMENT_RE`) since distinguishing them needs
+/// to tolerate the variable whitespace mypy itself accepts after the colon,
+/// which a fixed-string prefix table can't express.
+static DIRECTIVE_PREFIXES: &[PrefixEntry] = &[
+    PrefixEntry {
+        prefix: "noqa",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "fmt: off",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "fmt: on",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "fmt: skip",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "isort:",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "ruff:",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "mypy:",
+        kind: PyCommentKind::Directive,
+    },
+    PrefixEntry {
+        prefix: "pyright:",
+        kind: PyCommentKind::Directive,
+    },
+];
+
+/// PEP 263: `# -*- coding: <encoding-name> -*-` (and the simpler `# coding: <encoding-name>`).
+///
+/// Deliberately not anchored to the start of the trimmed text: PEP 263 allows
+/// the `coding[:=]` marker to appear anywhere on the line, most commonly
+/// wrapped in an editor-recognized `-*- ... -*-` sigil.
+static CODING_COOKIE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"coding[:=][ \t]*([-_.a-zA-Z0-9]+)").unwrap());
+
+/// Matches a PEP 484 inline type comment's `type:` marker, capturing whatever
+/// (possibly whitespace-separated) word immediately follows so the caller can
+/// tell a bare `# type: ignore` apart from `# type: ignore_me_not` (a
+/// real, if unusual, type expression naming a class called `ignore_me_not`).
+static TYPE_COMMENT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^type:\s*(\w*)").unwrap());
+
+impl PyCommentKind {
+    /// Classify a comment token.
+    ///
+    /// `text` is the full comment token text, including the leading `#`.
+    /// `range` is the comment's range in the source, used to detect a
+    /// shebang (which is only valid at the very start of the file).
+    /// `line_index` is the zero-based index of the physical line the comment
+    /// starts on, used to detect a PEP 263 coding cookie (only valid on the
+    /// first two lines of the file).
+    pub fn from_comment(text: &str, range: TextRange, line_index: usize) -> Self {
+        if range.start() == TextSize::new(0) && text.starts_with("#!") {
+            return PyCommentKind::Shebang;
+        }
+
+        let trimmed = text
+            .strip_prefix('#')
+            .unwrap_or(text)
+            .trim_start_matches(['#', ' ', '\t']);
+
+        if line_index < 2 && CODING_COOKIE_RE.is_match(trimmed) {
+            return PyCommentKind::CodingCookie;
+        }
+
+        if let Some(captures) = TYPE_COMMENT_RE.captures(trimmed) {
+            return if &captures[1] == "ignore" {
+                PyCommentKind::TypeIgnore
+            } else {
+                PyCommentKind::Type
+            };
+        }
+
+        for entry in DIRECTIVE_PREFIXES {
+            if trimmed.starts_with(entry.prefix) {
+                return entry.kind;
+            }
+        }
+
+        PyCommentKind::Ordinary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyCommentKind;
+    use ruff_text_size::{TextRange, TextSize};
+
+    fn classify(text: &str, offset: u32, line_index: usize) -> PyCommentKind {
+        let start = TextSize::new(offset);
+        let range = TextRange::new(start, start + TextSize::of(text));
+        PyCommentKind::from_comment(text, range, line_index)
+    }
+
+    #[test]
+    fn shebang_only_at_start_of_file() {
+        assert_eq!(classify("#!/usr/bin/env python", 0, 0), PyCommentKind::Shebang);
+        assert_eq!(classify("#!/usr/bin/env python", 1, 0), PyCommentKind::Ordinary);
+    }
+
+    #[test]
+    fn coding_cookie_only_on_first_two_lines() {
+        assert_eq!(
+            classify("# -*- coding: utf-8 -*-", 0, 0),
+            PyCommentKind::CodingCookie
+        );
+        assert_eq!(
+            classify("# coding=utf-8", 10, 1),
+            PyCommentKind::CodingCookie
+        );
+        assert_eq!(
+            classify("# -*- coding: utf-8 -*-", 100, 2),
+            PyCommentKind::Ordinary
+        );
+    }
+
+    #[test]
+    fn type_comment_vs_type_ignore() {
+        assert_eq!(classify("# type: List[int]", 5, 10), PyCommentKind::Type);
+        assert_eq!(
+            classify("# type: ignore[no-redef]", 5, 10),
+            PyCommentKind::TypeIgnore
+        );
+    }
+
+    #[test]
+    fn type_ignore_tolerates_mypys_whitespace_variants() {
+        // mypy itself matches `#\s*type:\s*ignore`, so both of these must
+        // still be recognized as a suppression comment, not a type comment
+        // for a type expression starting with `ignore`.
+        assert_eq!(
+            classify("# type:ignore[no-redef]", 5, 10),
+            PyCommentKind::TypeIgnore
+        );
+        assert_eq!(
+            classify("# type:   ignore[no-redef]", 5, 10),
+            PyCommentKind::TypeIgnore
+        );
+    }
+
+    #[test]
+    fn type_comment_naming_a_type_called_ignore() {
+        // `ignore_me_not` is a plausible (if unusual) type name; only the
+        // exact word `ignore` should flip this to `TypeIgnore`.
+        assert_eq!(
+            classify("# type: ignore_me_not", 5, 10),
+            PyCommentKind::Type
+        );
+    }
+
+    #[test]
+    fn tooling_directives() {
+        assert_eq!(classify("# noqa: E501", 5, 10), PyCommentKind::Directive);
+        assert_eq!(classify("# fmt: off", 5, 10), PyCommentKind::Directive);
+        assert_eq!(classify("# isort: skip", 5, 10), PyCommentKind::Directive);
+        assert_eq!(classify("# ruff: noqa", 5, 10), PyCommentKind::Directive);
+    }
+
+    #[test]
+    fn ordinary_comment() {
+        assert_eq!(classify("# just a comment", 5, 10), PyCommentKind::Ordinary);
+    }
+}
@@ -4,29 +4,103 @@ use ruff_python_ast::PySourceType;
 use ruff_python_parser::lexer::lex;
 use ruff_python_parser::{AsMode, Tok, Tokenized};
 use ruff_python_trivia::CommentRanges;
-use ruff_text_size::TextRange;
+use ruff_text_size::{TextRange, TextSize};
+
+use crate::comment_kind::PyCommentKind;
 
 #[derive(Debug, Clone, Default)]
-pub struct CommentRangesBuilder {
+pub struct CommentRangesBuilder<'a> {
+    source: &'a str,
     ranges: Vec<TextRange>,
+    kinds: Vec<PyCommentKind>,
+    /// Number of newlines seen so far, used to compute the physical line a
+    /// comment starts on without re-scanning the source for every comment.
+    line_index: usize,
+    /// Start of the previously visited token. Counting from the *start* of
+    /// the previous token (rather than its end) up to the start of the
+    /// current one means newlines inside a multi-line token -- a
+    /// triple-quoted string, a multi-line f-string, a backslash continuation
+    /// -- get counted too, not just the gap between tokens.
+    prev_start: TextSize,
 }
 
-impl CommentRangesBuilder {
+impl<'a> CommentRangesBuilder<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            ..Self::default()
+        }
+    }
+
     pub fn visit_token(&mut self, token: &Tok, range: TextRange) {
+        if let Some(gap) = self.source.get(self.prev_start.to_usize()..range.start().to_usize()) {
+            self.line_index += gap.matches('\n').count();
+        }
+        self.prev_start = range.start();
+
         if token.is_comment() {
+            let text = &self.source[range];
+            self.kinds
+                .push(PyCommentKind::from_comment(text, range, self.line_index));
             self.ranges.push(range);
         }
     }
 
+    /// Finishes building, discarding the [`PyCommentKind`] classification.
+    ///
+    /// This keeps the original, unchanged `CommentRanges` type so existing
+    /// callers that only need ranges (not kinds) aren't affected by this
+    /// module's classification work. Callers that also want the kind of
+    /// each comment should use [`CommentRangesBuilder::finish_with_kinds`]
+    /// instead.
     pub fn finish(self) -> CommentRanges {
         CommentRanges::new(self.ranges)
     }
+
+    /// Like [`CommentRangesBuilder::finish`], but also returns the
+    /// [`PyCommentKind`] classification computed for each comment.
+    pub fn finish_with_kinds(self) -> (CommentRanges, CommentKinds) {
+        // Pair up the two parallel vectors before `self.ranges` is moved into
+        // `CommentRanges::new` below; `entries` holds its own copy of each
+        // range alongside its kind.
+        let entries = self
+            .ranges
+            .iter()
+            .copied()
+            .zip(self.kinds.iter().copied())
+            .collect();
+        (CommentRanges::new(self.ranges), CommentKinds { entries })
+    }
+}
+
+/// A [`PyCommentKind`] classification for each comment recorded by a
+/// [`CommentRangesBuilder`], keyed by the comment's `TextRange`.
+///
+/// Kept separate from [`CommentRanges`] (rather than extending that type
+/// directly) since `CommentRanges` is a shared type with callers outside of
+/// this crate that have no use for the classification.
+#[derive(Debug, Clone, Default)]
+pub struct CommentKinds {
+    /// Sorted by range (the lexer emits tokens in source order), so `kind`
+    /// can binary-search rather than scan linearly.
+    entries: Vec<(TextRange, PyCommentKind)>,
+}
+
+impl CommentKinds {
+    /// Returns the kind of the comment at `range`, or [`PyCommentKind::Ordinary`]
+    /// if `range` wasn't recorded as a comment.
+    pub fn kind(&self, range: TextRange) -> PyCommentKind {
+        self.entries
+            .binary_search_by_key(&range.start(), |(entry_range, _)| entry_range.start())
+            .ok()
+            .map_or(PyCommentKind::Ordinary, |index| self.entries[index].1)
+    }
 }
 
 /// Helper method to lex and extract comment ranges
 pub fn tokens_and_ranges(source: &str, source_type: PySourceType) -> (Tokenized, CommentRanges) {
     let mut tokens = Vec::new();
-    let mut comment_ranges = CommentRangesBuilder::default();
+    let mut comment_ranges = CommentRangesBuilder::new(source);
     let mut lexer = lex(source, source_type.as_mode());
 
     for result in lexer.by_ref() {
@@ -38,3 +112,51 @@ pub fn tokens_and_ranges(source: &str, source_type: PySourceType) -> (Tokenized,
     let comment_ranges = comment_ranges.finish();
     (Tokenized::new(tokens, lexer.into_errors()), comment_ranges)
 }
+
+/// Like [`tokens_and_ranges`], but also returns the [`PyCommentKind`]
+/// classification for each comment. Additive alongside `tokens_and_ranges`
+/// so existing callers of that function are unaffected.
+pub fn tokens_and_ranges_with_kinds(
+    source: &str,
+    source_type: PySourceType,
+) -> (Tokenized, CommentRanges, CommentKinds) {
+    let mut tokens = Vec::new();
+    let mut comment_ranges = CommentRangesBuilder::new(source);
+    let mut lexer = lex(source, source_type.as_mode());
+
+    for result in lexer.by_ref() {
+        comment_ranges.visit_token(&result.0, result.1);
+
+        tokens.push(result);
+    }
+
+    let (ranges, kinds) = comment_ranges.finish_with_kinds();
+    (Tokenized::new(tokens, lexer.into_errors()), ranges, kinds)
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::PySourceType;
+
+    use crate::comment_kind::PyCommentKind;
+
+    use super::tokens_and_ranges_with_kinds;
+
+    #[test]
+    fn counts_newlines_inside_multiline_tokens() {
+        // The triple-quoted string spans physical lines 0-3, so the comment
+        // below it starts on line 4 and must not be treated as a PEP 263
+        // coding cookie, even though it matches the `coding:` pattern.
+        let source = "x = \"\"\"\na\nb\n\"\"\"\n# coding: utf-8\n";
+
+        let (tokens, _ranges, kinds) = tokens_and_ranges_with_kinds(source, PySourceType::Python);
+
+        let comment_range = tokens
+            .tokens()
+            .iter()
+            .find_map(|(token, range)| token.is_comment().then_some(*range))
+            .expect("source contains a comment");
+
+        assert_eq!(kinds.kind(comment_range), PyCommentKind::Ordinary);
+    }
+}